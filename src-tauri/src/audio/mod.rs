@@ -0,0 +1,2 @@
+pub mod resample;
+pub mod vad;
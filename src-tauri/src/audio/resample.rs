@@ -0,0 +1,155 @@
+//! Band-limited resampling so transcription quality doesn't silently degrade
+//! when a capture device's native sample rate isn't the 16kHz Whisper expects.
+//!
+//! Naive decimation (dropping samples) or duplication introduces aliasing;
+//! this instead evaluates a windowed-sinc kernel per output sample, which
+//! band-limits the signal before resampling it.
+
+/// Sample rate the STT backend expects
+pub const TARGET_SAMPLE_RATE: u32 = 16000;
+
+/// Number of sinc lobes on each side of the kernel center at a 1:1 rate;
+/// higher means a sharper cutoff and less aliasing, at the cost of more
+/// compute per output sample
+const SINC_HALF_WIDTH: f32 = 8.0;
+
+/// Downmix interleaved multi-channel audio to mono and resample to
+/// [`TARGET_SAMPLE_RATE`], ready for WAV encoding and upload
+pub fn resample_to_16k_mono(samples: &[f32], source_sample_rate: u32, channels: u16) -> Vec<f32> {
+    resample_to_rate_mono(samples, source_sample_rate, channels, TARGET_SAMPLE_RATE)
+}
+
+/// Downmix interleaved multi-channel audio to mono and resample to
+/// `target_rate`. Generalizes [`resample_to_16k_mono`] to an arbitrary
+/// target, e.g. matching a playback device's native rate instead of the STT
+/// backend's fixed 16kHz.
+pub(crate) fn resample_to_rate_mono(
+    samples: &[f32],
+    source_sample_rate: u32,
+    channels: u16,
+    target_rate: u32,
+) -> Vec<f32> {
+    let mono = downmix_to_mono(samples, channels);
+
+    if source_sample_rate == target_rate {
+        return mono;
+    }
+
+    resample_windowed_sinc(&mono, source_sample_rate, target_rate)
+}
+
+/// Average interleaved channel samples down to a single mono channel
+fn downmix_to_mono(samples: &[f32], channels: u16) -> Vec<f32> {
+    if channels <= 1 {
+        return samples.to_vec();
+    }
+
+    let channels = channels as usize;
+    samples
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect()
+}
+
+fn sinc(x: f32) -> f32 {
+    if x.abs() < 1e-7 {
+        1.0
+    } else {
+        (std::f32::consts::PI * x).sin() / (std::f32::consts::PI * x)
+    }
+}
+
+/// Blackman window over `[-half_width, half_width]`, tapering the sinc kernel
+/// to zero at its edges instead of truncating it abruptly
+fn blackman_window(x: f32, half_width: f32) -> f32 {
+    let n = (x + half_width) / (2.0 * half_width);
+    0.42 - 0.5 * (2.0 * std::f32::consts::PI * n).cos()
+        + 0.08 * (4.0 * std::f32::consts::PI * n).cos()
+}
+
+/// Resample via a windowed-sinc (band-limited) interpolation kernel evaluated
+/// at each output sample position
+fn resample_windowed_sinc(samples: &[f32], source_rate: u32, target_rate: u32) -> Vec<f32> {
+    if samples.is_empty() || source_rate == target_rate {
+        return samples.to_vec();
+    }
+
+    let ratio = target_rate as f64 / source_rate as f64;
+    let out_len = ((samples.len() as f64) * ratio).round().max(0.0) as usize;
+
+    // Downsampling needs a proportionally wider kernel to stay band-limited
+    let kernel_scale = (ratio as f32).min(1.0);
+    let half_width = SINC_HALF_WIDTH / kernel_scale.max(1e-6);
+
+    let mut output = Vec::with_capacity(out_len);
+
+    for out_index in 0..out_len {
+        let source_pos = out_index as f64 / ratio;
+        let center = source_pos.floor() as i64;
+        let frac = (source_pos - center as f64) as f32;
+
+        let lo = (center - half_width.ceil() as i64).max(0);
+        let hi = (center + half_width.ceil() as i64).min(samples.len() as i64 - 1);
+
+        let mut acc = 0.0f32;
+        let mut weight_sum = 0.0f32;
+        let mut i = lo;
+        while i <= hi {
+            let distance = (i - center) as f32 - frac;
+            let weight = sinc(distance * kernel_scale) * blackman_window(distance, half_width);
+            acc += samples[i as usize] * weight;
+            weight_sum += weight;
+            i += 1;
+        }
+
+        output.push(if weight_sum.abs() > 1e-7 { acc / weight_sum } else { 0.0 });
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_rate_is_passthrough() {
+        let samples = vec![0.1, 0.2, 0.3, 0.4];
+        let result = resample_to_16k_mono(&samples, 16000, 1);
+        assert_eq!(result, samples);
+    }
+
+    #[test]
+    fn test_downmix_stereo_to_mono() {
+        // Interleaved L/R where every frame averages to 0.5
+        let stereo = vec![0.0, 1.0, 0.0, 1.0, 0.0, 1.0];
+        let mono = downmix_to_mono(&stereo, 2);
+        assert_eq!(mono.len(), 3);
+        for sample in mono {
+            assert!((sample - 0.5).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_downsample_48k_to_16k_preserves_length_ratio() {
+        let samples = vec![0.0f32; 48000];
+        let result = resample_windowed_sinc(&samples, 48000, 16000);
+        assert_eq!(result.len(), 16000);
+    }
+
+    #[test]
+    fn test_upsample_8k_to_16k_preserves_length_ratio() {
+        let samples = vec![0.0f32; 8000];
+        let result = resample_windowed_sinc(&samples, 8000, 16000);
+        assert_eq!(result.len(), 16000);
+    }
+
+    #[test]
+    fn test_resample_silence_stays_silent() {
+        let samples = vec![0.0f32; 4410];
+        let result = resample_windowed_sinc(&samples, 44100, 16000);
+        for sample in result {
+            assert!(sample.abs() < 1e-5);
+        }
+    }
+}
@@ -0,0 +1,360 @@
+//! Spectral-energy voice-activity detection backing `RecordingMode::VoiceActivated`.
+//!
+//! Incoming audio is sliced into overlapping frames, each converted to a
+//! short-time power spectrum via a real FFT. Summing the magnitude-squared
+//! bins inside the speech band yields a per-frame energy value, which is
+//! compared against an adaptive noise floor to decide whether speech is in
+//! progress. The caller feeds samples incrementally and reacts to the
+//! `VadEvent`s this produces to gate capture and route only speech segments
+//! to `transcribe`.
+//!
+//! STAGED (library-only, not yet reachable end-to-end): this module is a
+//! complete, tested detector, but nothing outside `#[cfg(test)]` constructs
+//! one. The audio manager that owns the capture stream isn't present in this
+//! checkout, so `RecordingMode::VoiceActivated` is NOT functional yet — it
+//! should construct a `VoiceActivityDetector` from
+//! `voice_activated_silence_timeout` when that mode is active, feed it
+//! frames as they arrive from the input device, and react to `VadEvent` to
+//! gate capture and route only speech segments to `transcribe`. Land that
+//! call site before considering voice-activated mode done.
+
+use anyhow::{anyhow, Result};
+use realfft::{num_complex::Complex, RealToComplex, RealFftPlanner};
+use std::sync::Arc;
+
+/// Frame size in samples (~32ms at 16kHz)
+const FRAME_SIZE: usize = 512;
+/// 50% overlap between consecutive frames
+const HOP_SIZE: usize = FRAME_SIZE / 2;
+/// Frames used to seed the noise floor before the detector is armed. Ambient
+/// room noise (fans, hum, background chatter) routinely exceeds
+/// `EPSILON * speech_threshold_factor`, so without this the very first frame
+/// would latch the detector into `Speech` and the EMA would never run.
+const CALIBRATION_FRAMES: u32 = 10;
+
+/// Events emitted by the VAD as it observes frames of incoming audio
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VadEvent {
+    SpeechStarted,
+    SpeechEnded,
+}
+
+/// Tunable parameters for the voice-activity detector
+#[derive(Debug, Clone)]
+pub struct VadConfig {
+    pub sample_rate: u32,
+    /// Frame energy must exceed `noise_floor * speech_threshold_factor` to count as speech
+    pub speech_threshold_factor: f32,
+    /// Smoothing factor for the adaptive noise-floor EMA, in `[0, 1]`
+    pub noise_floor_alpha: f32,
+    /// Minimum duration speech must persist before it's reported, rejecting clicks/pops
+    pub min_speech_duration_ms: u32,
+    /// How long energy must stay below threshold before speech is considered ended;
+    /// mirrors `voice_activated_silence_timeout`
+    pub silence_timeout_ms: u32,
+    /// Low/high cutoff of the speech band in Hz used to select contributing FFT bins
+    pub speech_band_hz: (f32, f32),
+}
+
+impl Default for VadConfig {
+    fn default() -> Self {
+        Self {
+            sample_rate: 16000,
+            speech_threshold_factor: 2.5,
+            noise_floor_alpha: 0.05,
+            min_speech_duration_ms: 60,
+            silence_timeout_ms: 2000,
+            speech_band_hz: (100.0, 4000.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VadState {
+    Silence,
+    PossibleSpeech,
+    Speech,
+    PossibleSilence,
+}
+
+/// Frame-by-frame spectral-energy voice-activity detector.
+///
+/// Feed raw samples incrementally via [`process`](Self::process); it buffers
+/// enough samples to form overlapping frames internally and returns any
+/// `VadEvent`s triggered while processing them, in order. Includes hysteresis
+/// (`PossibleSpeech`/`PossibleSilence` intermediate states) so brief dips
+/// don't flicker the detector, and a minimum-speech-duration filter so clicks
+/// and pops never start a segment.
+pub struct VoiceActivityDetector {
+    config: VadConfig,
+    fft: Arc<dyn RealToComplex<f32>>,
+    window: Vec<f32>,
+    sample_buffer: Vec<f32>,
+    noise_floor: f32,
+    state: VadState,
+    speech_duration_ms: f32,
+    silence_duration_ms: f32,
+    hop_duration_ms: f32,
+    low_bin: usize,
+    high_bin: usize,
+    /// Frames consumed so far while seeding `noise_floor`; detection is
+    /// unarmed until this reaches `CALIBRATION_FRAMES`
+    calibration_frames_seen: u32,
+}
+
+impl VoiceActivityDetector {
+    pub fn new(config: VadConfig) -> Self {
+        let mut planner = RealFftPlanner::<f32>::new();
+        let fft = planner.plan_fft_forward(FRAME_SIZE);
+
+        // Hann window to reduce spectral leakage at frame edges
+        let window: Vec<f32> = (0..FRAME_SIZE)
+            .map(|i| {
+                0.5 - 0.5
+                    * ((2.0 * std::f32::consts::PI * i as f32) / (FRAME_SIZE as f32 - 1.0)).cos()
+            })
+            .collect();
+
+        let bin_hz = config.sample_rate as f32 / FRAME_SIZE as f32;
+        let low_bin = (config.speech_band_hz.0 / bin_hz).floor().max(0.0) as usize;
+        let high_bin = ((config.speech_band_hz.1 / bin_hz).ceil() as usize).min(FRAME_SIZE / 2);
+        // Each processed frame advances real time by HOP_SIZE samples, not FRAME_SIZE,
+        // since consecutive frames overlap by 50%
+        let hop_duration_ms = (HOP_SIZE as f32 / config.sample_rate as f32) * 1000.0;
+
+        Self {
+            config,
+            fft,
+            window,
+            sample_buffer: Vec::new(),
+            noise_floor: f32::EPSILON,
+            state: VadState::Silence,
+            speech_duration_ms: 0.0,
+            silence_duration_ms: 0.0,
+            hop_duration_ms,
+            low_bin,
+            high_bin,
+            calibration_frames_seen: 0,
+        }
+    }
+
+    /// Feed newly captured samples, returning any VAD events triggered while
+    /// consuming them
+    pub fn process(&mut self, samples: &[f32]) -> Result<Vec<VadEvent>> {
+        self.sample_buffer.extend_from_slice(samples);
+
+        let mut events = Vec::new();
+        while self.sample_buffer.len() >= FRAME_SIZE {
+            let frame = self.sample_buffer[..FRAME_SIZE].to_vec();
+            self.sample_buffer.drain(..HOP_SIZE);
+
+            let energy = self.frame_energy(&frame)?;
+            if let Some(event) = self.update_state(energy) {
+                events.push(event);
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// True if the detector currently believes speech is in progress
+    pub fn is_speaking(&self) -> bool {
+        matches!(self.state, VadState::Speech | VadState::PossibleSilence)
+    }
+
+    fn frame_energy(&self, frame: &[f32]) -> Result<f32> {
+        let mut windowed: Vec<f32> = frame.iter().zip(&self.window).map(|(s, w)| s * w).collect();
+
+        let mut spectrum: Vec<Complex<f32>> = self.fft.make_output_vec();
+        self.fft
+            .process(&mut windowed, &mut spectrum)
+            .map_err(|e| anyhow!("FFT processing failed: {}", e))?;
+
+        let high_bin = self.high_bin.min(spectrum.len().saturating_sub(1));
+        let energy = spectrum[self.low_bin..=high_bin]
+            .iter()
+            .map(|c| c.norm_sqr())
+            .sum();
+
+        Ok(energy)
+    }
+
+    fn update_state(&mut self, energy: f32) -> Option<VadEvent> {
+        // Seed the noise floor from raw ambient energy before arming
+        // detection, regardless of how that energy compares to a threshold
+        // derived from the not-yet-calibrated floor.
+        if self.calibration_frames_seen < CALIBRATION_FRAMES {
+            self.calibration_frames_seen += 1;
+            self.noise_floor +=
+                (energy - self.noise_floor) / self.calibration_frames_seen as f32;
+            return None;
+        }
+
+        let is_above_threshold = energy > self.noise_floor * self.config.speech_threshold_factor;
+
+        // Only adapt the noise floor while we believe we're hearing non-speech
+        if !is_above_threshold {
+            self.noise_floor = self.noise_floor * (1.0 - self.config.noise_floor_alpha)
+                + energy * self.config.noise_floor_alpha;
+        }
+
+        match self.state {
+            VadState::Silence => {
+                if is_above_threshold {
+                    self.state = VadState::PossibleSpeech;
+                    self.speech_duration_ms = self.hop_duration_ms;
+                }
+                None
+            }
+            VadState::PossibleSpeech => {
+                if is_above_threshold {
+                    self.speech_duration_ms += self.hop_duration_ms;
+                    if self.speech_duration_ms >= self.config.min_speech_duration_ms as f32 {
+                        self.state = VadState::Speech;
+                        return Some(VadEvent::SpeechStarted);
+                    }
+                    None
+                } else {
+                    // Energy dropped before the minimum duration elapsed: a click/pop, not speech
+                    self.state = VadState::Silence;
+                    self.speech_duration_ms = 0.0;
+                    None
+                }
+            }
+            VadState::Speech => {
+                if is_above_threshold {
+                    None
+                } else {
+                    self.state = VadState::PossibleSilence;
+                    self.silence_duration_ms = self.hop_duration_ms;
+                    None
+                }
+            }
+            VadState::PossibleSilence => {
+                if is_above_threshold {
+                    // Speech resumed before the timeout elapsed; reset the countdown
+                    self.state = VadState::Speech;
+                    self.silence_duration_ms = 0.0;
+                    None
+                } else {
+                    self.silence_duration_ms += self.hop_duration_ms;
+                    if self.silence_duration_ms >= self.config.silence_timeout_ms as f32 {
+                        self.state = VadState::Silence;
+                        self.silence_duration_ms = 0.0;
+                        return Some(VadEvent::SpeechEnded);
+                    }
+                    None
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(frequency: f32, sample_rate: u32, num_samples: usize, amplitude: f32) -> Vec<f32> {
+        (0..num_samples)
+            .map(|i| {
+                amplitude
+                    * (2.0 * std::f32::consts::PI * frequency * i as f32 / sample_rate as f32)
+                        .sin()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_silence_produces_no_events() {
+        let mut vad = VoiceActivityDetector::new(VadConfig::default());
+        let silence = vec![0.0f32; 16000];
+        let events = vad.process(&silence).unwrap();
+        assert!(events.is_empty());
+        assert!(!vad.is_speaking());
+    }
+
+    #[test]
+    fn test_sustained_tone_triggers_speech_started() {
+        let config = VadConfig {
+            min_speech_duration_ms: 60,
+            ..Default::default()
+        };
+        let mut vad = VoiceActivityDetector::new(config);
+
+        // Warm up the noise floor on silence first
+        vad.process(&vec![0.0f32; 8000]).unwrap();
+
+        // A loud, in-band tone sustained for well over the minimum speech duration
+        let speech = tone(800.0, 16000, 16000, 0.8);
+        let events = vad.process(&speech).unwrap();
+
+        assert!(events.contains(&VadEvent::SpeechStarted));
+        assert!(vad.is_speaking());
+    }
+
+    #[test]
+    fn test_speech_then_silence_triggers_speech_ended() {
+        let config = VadConfig {
+            min_speech_duration_ms: 60,
+            silence_timeout_ms: 100,
+            ..Default::default()
+        };
+        let mut vad = VoiceActivityDetector::new(config);
+
+        vad.process(&vec![0.0f32; 8000]).unwrap();
+        let speech = tone(800.0, 16000, 16000, 0.8);
+        vad.process(&speech).unwrap();
+        assert!(vad.is_speaking());
+
+        let silence = vec![0.0f32; 16000];
+        let events = vad.process(&silence).unwrap();
+
+        assert!(events.contains(&VadEvent::SpeechEnded));
+        assert!(!vad.is_speaking());
+    }
+
+    #[test]
+    fn test_silence_timeout_matches_real_elapsed_time() {
+        let config = VadConfig {
+            min_speech_duration_ms: 60,
+            silence_timeout_ms: 1000,
+            ..Default::default()
+        };
+        let mut vad = VoiceActivityDetector::new(config);
+
+        vad.process(&vec![0.0f32; 8000]).unwrap();
+        let speech = tone(800.0, 16000, 16000, 0.8);
+        vad.process(&speech).unwrap();
+        assert!(vad.is_speaking());
+
+        // 500ms of real silence: well under the 1000ms timeout, so speech must
+        // still be considered in progress (a frame-duration-based counter would
+        // double-count this as ~1000ms and end speech early)
+        let half_timeout_silence = vec![0.0f32; 8000];
+        let events = vad.process(&half_timeout_silence).unwrap();
+        assert!(!events.contains(&VadEvent::SpeechEnded));
+        assert!(vad.is_speaking());
+
+        // Another 600ms pushes real elapsed silence past the 1000ms timeout
+        let remaining_silence = vec![0.0f32; 9600];
+        let events = vad.process(&remaining_silence).unwrap();
+        assert!(events.contains(&VadEvent::SpeechEnded));
+    }
+
+    #[test]
+    fn test_brief_click_does_not_trigger_speech() {
+        let config = VadConfig {
+            min_speech_duration_ms: 200,
+            ..Default::default()
+        };
+        let mut vad = VoiceActivityDetector::new(config);
+
+        vad.process(&vec![0.0f32; 8000]).unwrap();
+
+        // A single loud frame, far shorter than the minimum speech duration
+        let click = tone(800.0, 16000, FRAME_SIZE, 0.9);
+        let events = vad.process(&click).unwrap();
+
+        assert!(!events.contains(&VadEvent::SpeechStarted));
+    }
+}
@@ -0,0 +1,4 @@
+pub mod openai;
+pub mod provider;
+pub mod speech;
+pub mod subtitles;
@@ -0,0 +1,156 @@
+//! Common interface implemented by every cloud speech-to-text backend, so the
+//! recording pipeline can depend on a single trait object rather than the
+//! concrete `OpenAIClient`, the same way `post_process_providers` decouples
+//! post-processing from any one LLM vendor.
+//!
+//! STAGED (library-only, not yet reachable end-to-end): `settings.rs` isn't
+//! present in this checkout, so `CloudTranscriptionProviderConfig` is never
+//! persisted or read by any settings code, and [`build_provider`] is called
+//! only from `#[cfg(test)]` — the recording pipeline has no way to pick a
+//! provider yet. Once `AppSettings` exists, it should grow a
+//! `cloud_transcription_providers: Vec<CloudTranscriptionProviderConfig>`
+//! and `cloud_transcription_provider_id: String` pair (mirroring
+//! `post_process_providers`/`post_process_provider_id`), and the recording
+//! pipeline should call `build_provider` with them instead of constructing
+//! an `OpenAIClient` directly.
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+
+use super::openai::{OpenAIClient, OpenAIConfiguration};
+
+/// A cloud speech-to-text backend
+#[async_trait]
+pub trait CloudTranscriptionProvider: Send + Sync {
+    /// Stable identifier stored in settings (e.g. `"openai"`)
+    fn id(&self) -> &str;
+
+    /// Human-readable name shown in the UI
+    fn display_name(&self) -> &str;
+
+    /// Transcribe raw audio samples to text. `source_sample_rate`/
+    /// `source_channels` describe the format the samples were captured in.
+    async fn transcribe(
+        &self,
+        audio_samples: Vec<f32>,
+        source_sample_rate: u32,
+        source_channels: u16,
+        language: Option<String>,
+        translate_to_english: bool,
+    ) -> Result<String>;
+
+    /// Verify the configured credentials work
+    async fn validate_credentials(&self) -> Result<()>;
+}
+
+#[async_trait]
+impl CloudTranscriptionProvider for OpenAIClient {
+    fn id(&self) -> &str {
+        "openai"
+    }
+
+    fn display_name(&self) -> &str {
+        "OpenAI Whisper"
+    }
+
+    async fn transcribe(
+        &self,
+        audio_samples: Vec<f32>,
+        source_sample_rate: u32,
+        source_channels: u16,
+        language: Option<String>,
+        translate_to_english: bool,
+    ) -> Result<String> {
+        OpenAIClient::transcribe(
+            self,
+            audio_samples,
+            source_sample_rate,
+            source_channels,
+            language,
+            translate_to_english,
+        )
+        .await
+    }
+
+    async fn validate_credentials(&self) -> Result<()> {
+        self.validate_api_key().await
+    }
+}
+
+/// A single provider's settings entry, the cloud-transcription analogue of the
+/// existing `post_process_providers` / `post_process_provider_id` settings pair
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CloudTranscriptionProviderConfig {
+    pub id: String,
+    pub display_name: String,
+    pub api_key: String,
+    pub model: String,
+    pub api_base: Option<String>,
+}
+
+impl From<&CloudTranscriptionProviderConfig> for OpenAIConfiguration {
+    fn from(config: &CloudTranscriptionProviderConfig) -> Self {
+        OpenAIConfiguration {
+            api_key: config.api_key.clone(),
+            model: config.model.clone(),
+            api_base: config.api_base.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Build the active `CloudTranscriptionProvider` from the configured provider
+/// list and the id of the one the user selected, mirroring how
+/// `post_process_provider_id` picks the active post-processing provider
+pub fn build_provider(
+    providers: &[CloudTranscriptionProviderConfig],
+    active_provider_id: &str,
+) -> Result<Box<dyn CloudTranscriptionProvider>> {
+    let config = providers
+        .iter()
+        .find(|p| p.id == active_provider_id)
+        .ok_or_else(|| anyhow!("No configured provider with id '{}'", active_provider_id))?;
+
+    match config.id.as_str() {
+        "openai" => Ok(Box::new(OpenAIClient::new_with_config(config.into())?)),
+        other => Err(anyhow!("Unknown cloud transcription provider '{}'", other)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn openai_provider_config() -> CloudTranscriptionProviderConfig {
+        CloudTranscriptionProviderConfig {
+            id: "openai".to_string(),
+            display_name: "OpenAI Whisper".to_string(),
+            api_key: "test-key".to_string(),
+            model: "whisper-1".to_string(),
+            api_base: None,
+        }
+    }
+
+    #[test]
+    fn test_build_provider_selects_by_id() {
+        let providers = vec![openai_provider_config()];
+        let provider = build_provider(&providers, "openai").unwrap();
+        assert_eq!(provider.id(), "openai");
+        assert_eq!(provider.display_name(), "OpenAI Whisper");
+    }
+
+    #[test]
+    fn test_build_provider_unknown_id() {
+        let providers = vec![openai_provider_config()];
+        let result = build_provider(&providers, "deepgram");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_provider_config_to_openai_configuration() {
+        let config = openai_provider_config();
+        let openai_config: OpenAIConfiguration = (&config).into();
+        assert_eq!(openai_config.api_key, "test-key");
+        assert_eq!(openai_config.model, "whisper-1");
+    }
+}
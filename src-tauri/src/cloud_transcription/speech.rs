@@ -0,0 +1,288 @@
+//! Text-to-speech feedback via OpenAI's speech endpoint, complementing the
+//! existing canned `audio_feedback` sounds with spoken confirmations — e.g.
+//! reading back transcribed text or announcing a mode change. Strictly
+//! opt-in, gated behind `SpeechConfiguration::tts_feedback_enabled`.
+//!
+//! [`SpeechClient::speak`] decodes the synthesized WAV and plays it on the
+//! system's default output device via `cpal`.
+//!
+//! STAGED (library-only, not yet reachable end-to-end): nothing outside
+//! `#[cfg(test)]` calls `speak`, since the caller that should invoke it on a
+//! transcription completing or a mode change isn't present in this
+//! checkout, so no spoken feedback is actually emitted yet. Separately,
+//! `AppSettings` and `selected_output_device` aren't present either, so
+//! playback always targets the default output device rather than a
+//! user-chosen one. Once those exist: thread the selected device name into
+//! [`play_wav_bytes`] instead of `cpal::default_host().default_output_device()`,
+//! and call `speak` from wherever mode-change/transcription-complete
+//! feedback is already triggered.
+
+use anyhow::{anyhow, Result};
+use async_openai::{
+    config::OpenAIConfig,
+    types::{AudioSpeechResponseFormat, CreateSpeechRequestArgs, Voice},
+    Client,
+};
+use crate::audio::resample;
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use log::error;
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+use std::time::Duration;
+
+/// Configuration for OpenAI text-to-speech feedback
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpeechConfiguration {
+    pub api_key: String,
+    pub model: String,
+    pub voice: String,
+    /// Optional base URL, mirroring `OpenAIConfiguration::api_base`, so TTS
+    /// feedback follows the same self-hosted/proxy endpoint as transcription
+    /// instead of always hitting OpenAI's public API
+    pub api_base: Option<String>,
+    /// Master opt-in switch for spoken feedback. [`SpeechClient::speak`] is a
+    /// no-op while this is `false`, so enabling TTS never requires touching
+    /// call sites — only this setting.
+    pub tts_feedback_enabled: bool,
+}
+
+impl Default for SpeechConfiguration {
+    fn default() -> Self {
+        Self {
+            api_key: String::new(),
+            model: "tts-1".to_string(),
+            voice: "alloy".to_string(),
+            api_base: None,
+            tts_feedback_enabled: false,
+        }
+    }
+}
+
+/// Client for synthesizing spoken feedback via OpenAI's speech endpoint
+#[derive(Clone)]
+pub struct SpeechClient {
+    client: Client<OpenAIConfig>,
+    config: SpeechConfiguration,
+}
+
+impl SpeechClient {
+    /// Create a new speech client, reusing the configured `openai_api_key`
+    pub fn new(config: SpeechConfiguration) -> Result<Self> {
+        if config.api_key.is_empty() {
+            return Err(anyhow!("OpenAI API key is required for speech feedback"));
+        }
+
+        let mut openai_config = OpenAIConfig::new().with_api_key(config.api_key.clone());
+        if let Some(api_base) = &config.api_base {
+            openai_config = openai_config.with_api_base(api_base.clone());
+        }
+        let client = Client::with_config(openai_config);
+
+        Ok(Self { client, config })
+    }
+
+    /// Synthesize `text` as spoken audio, returning the WAV bytes; [`speak`](Self::speak)
+    /// plays them on the default output device
+    pub async fn synthesize(&self, text: &str) -> Result<Vec<u8>> {
+        if text.trim().is_empty() {
+            return Err(anyhow!("Cannot synthesize empty text"));
+        }
+
+        let voice = parse_voice(&self.config.voice)?;
+
+        let request = CreateSpeechRequestArgs::default()
+            .model(self.config.model.clone())
+            .input(text)
+            .voice(voice)
+            .response_format(AudioSpeechResponseFormat::Wav)
+            .build()
+            .map_err(|e| anyhow!("Failed to build speech request: {}", e))?;
+
+        let response = self
+            .client
+            .audio()
+            .speech(request)
+            .await
+            .map_err(|e| anyhow!("OpenAI speech API error: {}", e))?;
+
+        Ok(response.bytes.to_vec())
+    }
+
+    /// Synthesize `text` and play it on the default output device, unless
+    /// `tts_feedback_enabled` is off, in which case this is a no-op
+    pub async fn speak(&self, text: &str) -> Result<()> {
+        if !self.config.tts_feedback_enabled {
+            return Ok(());
+        }
+
+        let wav_bytes = self.synthesize(text).await?;
+
+        // Playback blocks the calling thread for the clip's duration; run it
+        // on the blocking pool so it doesn't stall the tokio runtime's async
+        // worker threads the way every other call in this module assumes.
+        tokio::task::spawn_blocking(move || play_wav_bytes(&wav_bytes))
+            .await
+            .map_err(|e| anyhow!("TTS playback task panicked: {}", e))?
+    }
+}
+
+/// Decode WAV bytes, resample/upmix them to match the default output
+/// device's native config, and play them, blocking until playback finishes
+fn play_wav_bytes(wav_bytes: &[u8]) -> Result<()> {
+    let mut reader = hound::WavReader::new(Cursor::new(wav_bytes))
+        .map_err(|e| anyhow!("Failed to decode TTS WAV output: {}", e))?;
+    let spec = reader.spec();
+    let samples: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Int => {
+            let max_amplitude = (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|s| s.map(|s| s as f32 / max_amplitude))
+                .collect::<std::result::Result<_, _>>()
+        }
+        hound::SampleFormat::Float => reader.samples::<f32>().collect::<std::result::Result<_, _>>(),
+    }
+    .map_err(|e| anyhow!("Failed to read TTS WAV samples: {}", e))?;
+
+    let host = cpal::default_host();
+    let device = host
+        .default_output_device()
+        .ok_or_else(|| anyhow!("No default output device available for TTS playback"))?;
+
+    // The TTS WAV's sample rate/channel count rarely matches what the
+    // device actually supports (e.g. a 24kHz mono clip on a 48kHz stereo
+    // device), so retarget to the device's own default config rather than
+    // asserting the WAV's format and letting `build_output_stream` reject it.
+    let device_config = device
+        .default_output_config()
+        .map_err(|e| anyhow!("Failed to query default output config: {}", e))?;
+    let device_sample_rate = device_config.sample_rate().0;
+    let device_channels = device_config.channels();
+
+    let mono = resample::resample_to_rate_mono(
+        &samples,
+        spec.sample_rate,
+        spec.channels,
+        device_sample_rate,
+    );
+    let playback_secs = mono.len() as f32 / device_sample_rate as f32;
+    let interleaved: Vec<f32> = mono
+        .into_iter()
+        .flat_map(|sample| std::iter::repeat(sample).take(device_channels as usize))
+        .collect();
+
+    let config = cpal::StreamConfig {
+        channels: device_channels,
+        sample_rate: cpal::SampleRate(device_sample_rate),
+        buffer_size: cpal::BufferSize::Default,
+    };
+
+    let mut remaining = interleaved.into_iter();
+    let stream = device
+        .build_output_stream(
+            &config,
+            move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+                for slot in data.iter_mut() {
+                    *slot = remaining.next().unwrap_or(0.0);
+                }
+            },
+            |err| error!("TTS playback stream error: {}", err),
+            None,
+        )
+        .map_err(|e| anyhow!("Failed to build TTS playback stream: {}", e))?;
+
+    stream
+        .play()
+        .map_err(|e| anyhow!("Failed to start TTS playback: {}", e))?;
+
+    std::thread::sleep(Duration::from_secs_f32(playback_secs));
+
+    Ok(())
+}
+
+/// Map a configured voice name to the enum the speech API expects, tolerating
+/// surrounding whitespace and casing so a hand-edited or UI-stored config
+/// value isn't rejected over a trivial formatting difference
+fn parse_voice(voice: &str) -> Result<Voice> {
+    match voice.trim().to_lowercase().as_str() {
+        "alloy" => Ok(Voice::Alloy),
+        "echo" => Ok(Voice::Echo),
+        "fable" => Ok(Voice::Fable),
+        "onyx" => Ok(Voice::Onyx),
+        "nova" => Ok(Voice::Nova),
+        "shimmer" => Ok(Voice::Shimmer),
+        _ => Err(anyhow!("Unknown TTS voice '{}'", voice)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_speech_configuration_default() {
+        let config = SpeechConfiguration::default();
+        assert_eq!(config.model, "tts-1");
+        assert_eq!(config.voice, "alloy");
+        assert!(!config.tts_feedback_enabled);
+    }
+
+    #[tokio::test]
+    async fn test_speak_is_noop_when_disabled() {
+        let client = SpeechClient::new(SpeechConfiguration {
+            api_key: "test-key".to_string(),
+            tts_feedback_enabled: false,
+            ..Default::default()
+        })
+        .unwrap();
+
+        // With feedback disabled, `speak` must return without ever reaching
+        // the network call or the playback device.
+        assert!(client.speak("hello").await.is_ok());
+    }
+
+    #[test]
+    fn test_new_requires_api_key() {
+        let result = SpeechClient::new(SpeechConfiguration::default());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_voice_known() {
+        assert!(parse_voice("nova").is_ok());
+        assert!(parse_voice("shimmer").is_ok());
+    }
+
+    #[test]
+    fn test_parse_voice_tolerates_case_and_whitespace() {
+        assert!(parse_voice("Alloy").is_ok());
+        assert!(parse_voice(" nova \n").is_ok());
+    }
+
+    #[test]
+    fn test_new_with_custom_api_base() {
+        let config = SpeechConfiguration {
+            api_key: "test-key".to_string(),
+            api_base: Some("http://localhost:8080/v1".to_string()),
+            ..Default::default()
+        };
+        assert!(SpeechClient::new(config).is_ok());
+    }
+
+    #[test]
+    fn test_parse_voice_unknown() {
+        assert!(parse_voice("not-a-voice").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_synthesize_rejects_empty_text() {
+        let client = SpeechClient::new(SpeechConfiguration {
+            api_key: "test-key".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let result = client.synthesize("   ").await;
+        assert!(result.is_err());
+    }
+}
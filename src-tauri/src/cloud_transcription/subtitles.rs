@@ -0,0 +1,298 @@
+//! Formats verbose-transcription segment timing as standard subtitle files,
+//! and writes them to disk alongside a saved recording via
+//! [`write_subtitles_alongside_recording`].
+//!
+//! STAGED (library-only, not yet reachable end-to-end): the history/
+//! recording-save subsystem that owns writing the WAV to disk and knows
+//! `RecordingSaveMode` isn't present in this checkout, so the only caller of
+//! `write_subtitles_alongside_recording` is
+//! `VerboseTranscriptionResult::write_subtitles`, itself called only from
+//! `#[cfg(test)]` — a user
+//! gets no subtitle files from this alone. Whoever adds that subsystem must
+//! call it with the saved WAV path whenever `RecordingSaveMode` includes
+//! audio and verbose segments are available before this feature is done.
+
+use anyhow::Result;
+use std::path::Path;
+
+use crate::cloud_transcription::openai::TranscriptionSegment;
+
+/// Maximum number of characters on a single subtitle line before wrapping
+const MAX_LINE_CHARS: usize = 42;
+
+/// Render verbose-transcription segments as an SRT subtitle file
+pub fn segments_to_srt(segments: &[TranscriptionSegment]) -> String {
+    let mut out = String::new();
+
+    for (index, segment) in segments.iter().enumerate() {
+        out.push_str(&format!("{}\n", index + 1));
+        out.push_str(&format!(
+            "{} --> {}\n",
+            format_srt_timestamp(segment.start),
+            format_srt_timestamp(segment.end)
+        ));
+        out.push_str(&wrap_cue_text(&segment.text, false));
+        out.push_str("\n\n");
+    }
+
+    out
+}
+
+/// Render verbose-transcription segments as a WebVTT subtitle file
+pub fn segments_to_vtt(segments: &[TranscriptionSegment]) -> String {
+    let mut out = String::from("WEBVTT\n\n");
+
+    for (index, segment) in segments.iter().enumerate() {
+        out.push_str(&format!("{}\n", index + 1));
+        out.push_str(&format!(
+            "{} --> {}\n",
+            format_vtt_timestamp(segment.start),
+            format_vtt_timestamp(segment.end)
+        ));
+        out.push_str(&wrap_cue_text(&segment.text, true));
+        out.push_str("\n\n");
+    }
+
+    out
+}
+
+/// Write `segments` as sibling `.srt` and `.vtt` files next to `wav_path`,
+/// e.g. `recording.wav` produces `recording.srt` and `recording.vtt`. This is
+/// the save-path hook: call it with the path the recording was written to
+/// whenever verbose segment timing is available and the configured
+/// `RecordingSaveMode` calls for subtitle output. A no-op when `segments` is
+/// empty, since there's nothing meaningful to write.
+pub fn write_subtitles_alongside_recording(
+    wav_path: &Path,
+    segments: &[TranscriptionSegment],
+) -> Result<()> {
+    if segments.is_empty() {
+        return Ok(());
+    }
+
+    std::fs::write(wav_path.with_extension("srt"), segments_to_srt(segments))?;
+    std::fs::write(wav_path.with_extension("vtt"), segments_to_vtt(segments))?;
+    Ok(())
+}
+
+/// Format seconds as an SRT timestamp: `HH:MM:SS,mmm`
+fn format_srt_timestamp(seconds: f32) -> String {
+    let (hours, minutes, secs, millis) = split_seconds(seconds);
+    format!("{:02}:{:02}:{:02},{:03}", hours, minutes, secs, millis)
+}
+
+/// Format seconds as a WebVTT timestamp: `HH:MM:SS.mmm`
+fn format_vtt_timestamp(seconds: f32) -> String {
+    let (hours, minutes, secs, millis) = split_seconds(seconds);
+    format!("{:02}:{:02}:{:02}.{:03}", hours, minutes, secs, millis)
+}
+
+fn split_seconds(seconds: f32) -> (u32, u32, u32, u32) {
+    let total_millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    let hours = total_millis / 3_600_000;
+    let minutes = (total_millis % 3_600_000) / 60_000;
+    let secs = (total_millis % 60_000) / 1000;
+    let millis = total_millis % 1000;
+    (hours as u32, minutes as u32, secs as u32, millis as u32)
+}
+
+/// Escape cue-breaking characters and wrap long lines so no single line
+/// exceeds `MAX_LINE_CHARS`, splitting on word boundaries and, for a single
+/// token longer than the limit (e.g. a long URL), hard-splitting it too.
+///
+/// `escape_html` additionally escapes `&`/`<`/`>` as WebVTT cue text is
+/// parsed as a restricted HTML fragment, where those characters are
+/// otherwise malformed; SRT has no such grammar, so callers pass `false`
+/// for it.
+fn wrap_cue_text(text: &str, escape_html: bool) -> String {
+    let mut escaped = text.replace('\n', " ").replace("-->", "- ->");
+    if escape_html {
+        escaped = escaped
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;");
+    }
+    let words = escaped.split_whitespace();
+
+    let mut lines = Vec::new();
+    let mut current_line = String::new();
+    let mut current_len = 0usize;
+
+    for word in words {
+        for chunk in split_overlong_word(word) {
+            let chunk_len = chunk.chars().count();
+            if current_len > 0 && current_len + 1 + chunk_len > MAX_LINE_CHARS {
+                lines.push(std::mem::take(&mut current_line));
+                current_len = 0;
+            }
+            if current_len > 0 {
+                current_line.push(' ');
+                current_len += 1;
+            }
+            current_line.push_str(&chunk);
+            current_len += chunk_len;
+        }
+    }
+
+    if !current_line.is_empty() {
+        lines.push(current_line);
+    }
+
+    lines.join("\n")
+}
+
+/// Break `word` into `MAX_LINE_CHARS`-sized pieces if it alone exceeds the
+/// line limit, so the "no line exceeds `MAX_LINE_CHARS`" invariant holds even
+/// for unbreakable tokens
+fn split_overlong_word(word: &str) -> Vec<String> {
+    if word.chars().count() <= MAX_LINE_CHARS {
+        return vec![word.to_string()];
+    }
+
+    word.chars()
+        .collect::<Vec<_>>()
+        .chunks(MAX_LINE_CHARS)
+        .map(|chunk| chunk.iter().collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_segments() -> Vec<TranscriptionSegment> {
+        vec![
+            TranscriptionSegment {
+                id: 0,
+                start: 0.0,
+                end: 1.234,
+                text: "Hello world".to_string(),
+                avg_logprob: -0.1,
+                no_speech_prob: 0.01,
+            },
+            TranscriptionSegment {
+                id: 1,
+                start: 1.234,
+                end: 65.5,
+                text: "This is the second cue".to_string(),
+                avg_logprob: -0.2,
+                no_speech_prob: 0.02,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_srt_timestamp_formatting() {
+        assert_eq!(format_srt_timestamp(0.0), "00:00:00,000");
+        assert_eq!(format_srt_timestamp(65.5), "00:01:05,500");
+        assert_eq!(format_srt_timestamp(3661.25), "01:01:01,250");
+    }
+
+    #[test]
+    fn test_vtt_timestamp_formatting() {
+        assert_eq!(format_vtt_timestamp(0.0), "00:00:00.000");
+        assert_eq!(format_vtt_timestamp(65.5), "00:01:05.500");
+    }
+
+    #[test]
+    fn test_segments_to_srt() {
+        let srt = segments_to_srt(&sample_segments());
+        assert!(srt.contains("1\n00:00:00,000 --> 00:00:01,234\nHello world"));
+        assert!(srt.contains("2\n00:00:01,234 --> 00:01:05,500\nThis is the second cue"));
+    }
+
+    #[test]
+    fn test_segments_to_vtt() {
+        let vtt = segments_to_vtt(&sample_segments());
+        assert!(vtt.starts_with("WEBVTT\n\n"));
+        assert!(vtt.contains("1\n00:00:00.000 --> 00:00:01.234\nHello world"));
+    }
+
+    #[test]
+    fn test_wrap_cue_text_splits_long_lines() {
+        let long_text = "one two three four five six seven eight nine ten eleven twelve";
+        let wrapped = wrap_cue_text(long_text, false);
+        for line in wrapped.lines() {
+            assert!(line.len() <= MAX_LINE_CHARS);
+        }
+    }
+
+    #[test]
+    fn test_wrap_cue_text_hard_splits_overlong_token() {
+        let long_url = "a".repeat(100);
+        let wrapped = wrap_cue_text(&long_url, false);
+        for line in wrapped.lines() {
+            assert!(line.chars().count() <= MAX_LINE_CHARS);
+        }
+        assert_eq!(wrapped.chars().filter(|c| *c != '\n').count(), 100);
+    }
+
+    #[test]
+    fn test_wrap_cue_text_escapes_arrow() {
+        let wrapped = wrap_cue_text("a --> b", false);
+        assert!(!wrapped.contains("-->"));
+    }
+
+    #[test]
+    fn test_wrap_cue_text_escapes_html_for_vtt() {
+        let wrapped = wrap_cue_text("Tom & Jerry <laughs>", true);
+        assert_eq!(wrapped, "Tom &amp; Jerry &lt;laughs&gt;");
+    }
+
+    #[test]
+    fn test_wrap_cue_text_does_not_escape_html_for_srt() {
+        let wrapped = wrap_cue_text("Tom & Jerry <laughs>", false);
+        assert_eq!(wrapped, "Tom & Jerry <laughs>");
+    }
+
+    #[test]
+    fn test_write_subtitles_alongside_recording() {
+        let dir = std::env::temp_dir().join(format!(
+            "handy_subtitle_test_{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let wav_path = dir.join("recording.wav");
+
+        write_subtitles_alongside_recording(&wav_path, &sample_segments()).unwrap();
+
+        let srt = std::fs::read_to_string(dir.join("recording.srt")).unwrap();
+        let vtt = std::fs::read_to_string(dir.join("recording.vtt")).unwrap();
+        assert!(srt.contains("Hello world"));
+        assert!(vtt.starts_with("WEBVTT\n\n"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_write_subtitles_alongside_recording_skips_empty_segments() {
+        let dir = std::env::temp_dir().join(format!(
+            "handy_subtitle_test_empty_{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let wav_path = dir.join("recording.wav");
+
+        write_subtitles_alongside_recording(&wav_path, &[]).unwrap();
+
+        assert!(!dir.join("recording.srt").exists());
+        assert!(!dir.join("recording.vtt").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_segments_to_vtt_escapes_ampersand() {
+        let segments = vec![TranscriptionSegment {
+            id: 0,
+            start: 0.0,
+            end: 1.0,
+            text: "R&D <team>".to_string(),
+            avg_logprob: -0.1,
+            no_speech_prob: 0.01,
+        }];
+        let vtt = segments_to_vtt(&segments);
+        assert!(vtt.contains("R&amp;D &lt;team&gt;"));
+        assert!(!vtt.contains("R&D"));
+    }
+}
@@ -22,12 +22,12 @@ fn test_audio_conversion() {
     
     // Test with empty audio
     let empty_samples: Vec<f32> = vec![];
-    let result = client.audio_samples_to_wav_bytes(&empty_samples);
+    let result = client.audio_samples_to_wav_bytes(&empty_samples, 16000, 1);
     assert!(result.is_ok());
-    
+
     // Test with simple audio (1 second of silence at 16kHz)
     let samples = vec![0.0f32; 16000];
-    let result = client.audio_samples_to_wav_bytes(&samples);
+    let result = client.audio_samples_to_wav_bytes(&samples, 16000, 1);
     assert!(result.is_ok());
     
     let wav_bytes = result.unwrap();
@@ -85,6 +85,8 @@ fn test_openai_configuration_update() {
         language: Some("en".to_string()),
         temperature: 0.5,
         prompt: Some("Test prompt".to_string()),
+        api_base: None,
+        org_id: None,
     };
     
     // Note: update_config is async, so we can't test it directly in unit tests